@@ -0,0 +1,26 @@
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    #[event("stakeEvent")]
+    fn stake_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        amount: &BigUint,
+        new_total: &BigUint,
+    );
+
+    #[event("unstakeEvent")]
+    fn unstake_event(&self, #[indexed] caller: &ManagedAddress, amount: &BigUint);
+
+    #[event("claimEvent")]
+    fn claim_event(&self, #[indexed] caller: &ManagedAddress, reward: &BigUint);
+
+    #[event("poolUpdatedEvent")]
+    fn pool_updated_event(
+        &self,
+        acc_reward_per_share: &BigUint,
+        total_staked: &BigUint,
+        timestamp: u64,
+    );
+}
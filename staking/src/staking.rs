@@ -3,10 +3,14 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod events;
+
 #[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, PartialEq, Eq, TypeAbi, Clone)]
 pub struct Stake<M: ManagedTypeApi> {
     amount: BigUint<M>,
     reward_debt: BigUint<M>,
+    pub deferred_amount: BigUint<M>,
+    distribution_id: u64,
 }
 
 impl<M: ManagedTypeApi> Default for Stake<M> {
@@ -14,15 +18,24 @@ impl<M: ManagedTypeApi> Default for Stake<M> {
         Self {
             amount: BigUint::zero(),
             reward_debt: BigUint::zero(),
+            deferred_amount: BigUint::zero(),
+            distribution_id: 0,
         }
     }
 }
 
-pub const REWARD_PER_SECOND: u64 = 300; // Example value - meant to be 0.0003 ELGD, as requested
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, PartialEq, Eq, TypeAbi, Clone)]
+pub struct UnstakeRequest<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub unlock_time: u64,
+}
+
+pub const DEFAULT_REWARD_PER_SECOND: u64 = 300; // Example value - meant to be 0.0003 ELGD, as requested
+pub const ACC_PRECISION: u64 = 1_000_000_000_000; // 1e12, keeps acc_reward_per_share from flooring to 0
 
 
 #[multiversx_sc::contract]
-pub trait StakingContract {
+pub trait StakingContract: events::EventsModule {
 
     #[view(getStakingPosition)]
     #[storage_mapper("stakes")]
@@ -37,9 +50,80 @@ pub trait StakingContract {
     #[storage_mapper("lastRewardTime")]
     fn last_reward_time(&self) -> SingleValueMapper<u64>;
 
+    #[view(getUnbondPeriodSeconds)]
+    #[storage_mapper("unbondPeriodSeconds")]
+    fn unbond_period_seconds(&self) -> SingleValueMapper<u64>;
+
+    #[view(getUnstakeRequests)]
+    #[storage_mapper("unstakeRequests")]
+    fn unstake_requests(
+        &self,
+        caller: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<Self::Api, UnstakeRequest<Self::Api>>>;
+
+    #[view(getRewardReserve)]
+    #[storage_mapper("rewardReserve")]
+    fn reward_reserve(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalRewardsFunded")]
+    fn total_rewards_funded(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalRewardsPaid")]
+    fn total_rewards_paid(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getRewardPerSecond)]
+    #[storage_mapper("rewardPerSecond")]
+    fn reward_per_second(&self) -> SingleValueMapper<u64>;
+
+    #[view(isPaused)]
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("admins")]
+    fn admins(&self) -> SetMapper<ManagedAddress>;
+
+    #[view(isGapModeEnabled)]
+    #[storage_mapper("gapModeEnabled")]
+    fn gap_mode_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(getDistributionId)]
+    #[storage_mapper("distributionId")]
+    fn distribution_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("accRewardPerShareAtDistribution")]
+    fn acc_reward_per_share_at(&self, distribution_id: u64) -> SingleValueMapper<BigUint>;
+
+    // Counts stakes still deferred against a given distribution_id, so the matching
+    // acc_reward_per_share_at entry can be pruned the moment nothing references it
+    // anymore, instead of growing by one slot on every gap-mode tick forever.
+    #[storage_mapper("distributionRefCount")]
+    fn distribution_ref_count(&self, distribution_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("deferredTotalStaked")]
+    fn deferred_total_staked(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getStakingTokenId)]
+    #[storage_mapper("stakingTokenId")]
+    fn staking_token_id(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[view(getRewardTokenId)]
+    #[storage_mapper("rewardTokenId")]
+    fn reward_token_id(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
     #[init]
-    fn init(&self) {
+    fn init(
+        &self,
+        unbond_period_seconds: u64,
+        gap_mode_enabled: bool,
+        staking_token_id: EgldOrEsdtTokenIdentifier,
+        reward_token_id: EgldOrEsdtTokenIdentifier,
+    ) {
         self.last_reward_time().set(&self.blockchain().get_block_timestamp());
+        self.unbond_period_seconds().set(unbond_period_seconds);
+        self.reward_per_second().set(DEFAULT_REWARD_PER_SECOND);
+        self.gap_mode_enabled().set(gap_mode_enabled);
+        self.staking_token_id().set(staking_token_id);
+        self.reward_token_id().set(reward_token_id);
     }
 
     #[view(getTotalStaked)]
@@ -47,9 +131,16 @@ pub trait StakingContract {
     fn get_total_staked(&self) -> SingleValueMapper<BigUint>;
 
     #[endpoint]
-    #[payable("EGLD")]
+    #[payable("*")]
     fn stake(&self) {
-        let payment_amount = self.call_value().egld_value().clone_value();
+        require!(!self.paused().get(), "Contract is paused");
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(
+            payment.token_identifier == self.staking_token_id().get(),
+            "Invalid staking token"
+        );
+        let payment_amount = payment.amount;
         require!(payment_amount > 0, "Must pay more than 0");
 
         let caller = self.blockchain().get_caller();
@@ -60,16 +151,32 @@ pub trait StakingContract {
         } else {
             self.stakes(&caller).get()
         };
+        self.sync_stake(&mut stake);
 
         if stake.amount > 0 {
-            let pending_reward = &stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt;
+            let pending_reward =
+                (&stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt) / ACC_PRECISION;
             self.send_rewards(&caller, &pending_reward);
         }
-    
-        stake.amount += &payment_amount;
-        stake.reward_debt = &stake.amount * &self.acc_reward_per_share().get();
+
+        if self.gap_mode_enabled().get() {
+            let current_distribution_id = self.distribution_id().get();
+            if stake.deferred_amount == 0 {
+                self.distribution_ref_count(current_distribution_id)
+                    .update(|val| *val += 1);
+            }
+            stake.deferred_amount += &payment_amount;
+            stake.distribution_id = current_distribution_id;
+            stake.reward_debt = &stake.amount * &self.acc_reward_per_share().get();
+            self.deferred_total_staked().update(|val| *val += &payment_amount);
+        } else {
+            stake.amount += &payment_amount;
+            stake.reward_debt = &stake.amount * &self.acc_reward_per_share().get();
+            self.total_staked().update(|val| *val += &payment_amount);
+        }
+
         self.stakes(&caller).set(&stake);
-        self.total_staked().update(|val| *val += &payment_amount);
+        self.stake_event(&caller, &payment_amount, &self.total_staked().get());
     }
 
     fn update_pool(&self) {
@@ -80,69 +187,215 @@ pub trait StakingContract {
         }
 
         let total_staked = self.total_staked().get();
-        if total_staked == 0 {
-            self.last_reward_time().set(&current_time);
-            return;
+        if total_staked > 0 {
+            let elapsed_time = current_time - last_reward_time;
+            let uncapped_reward = BigUint::from(elapsed_time) * self.reward_per_second().get();
+            let reward_reserve = self.reward_reserve().get();
+            let reward = if uncapped_reward > reward_reserve {
+                reward_reserve
+            } else {
+                uncapped_reward
+            };
+            self.reward_reserve().update(|val| *val -= &reward);
+
+            let acc_reward_per_share =
+                self.acc_reward_per_share().get() + (reward * ACC_PRECISION) / &total_staked;
+            self.acc_reward_per_share().set(&acc_reward_per_share);
         }
 
-        let elapsed_time = current_time - last_reward_time;
-        let reward = BigUint::from(elapsed_time) * REWARD_PER_SECOND;
-        let acc_reward_per_share = self.acc_reward_per_share().get() + reward / &total_staked;
-        self.acc_reward_per_share().set(&acc_reward_per_share);
         self.last_reward_time().set(&current_time);
+
+        // Gap mode must graduate deferred deposits and advance distribution_id on every
+        // tick, even one where total_staked was 0, or a sole first staker's deferred
+        // amount would never graduate and its principal would be stuck forever.
+        if self.gap_mode_enabled().get() {
+            let closing_distribution_id = self.distribution_id().get();
+            if self.distribution_ref_count(closing_distribution_id).get() > 0 {
+                self.acc_reward_per_share_at(closing_distribution_id)
+                    .set(&self.acc_reward_per_share().get());
+            }
+
+            let graduating = self.deferred_total_staked().get();
+            if graduating > 0 {
+                self.total_staked().update(|val| *val += &graduating);
+                self.deferred_total_staked().set(&BigUint::zero());
+            }
+            self.distribution_id().update(|val| *val += 1);
+        }
+
+        self.pool_updated_event(
+            &self.acc_reward_per_share().get(),
+            &self.total_staked().get(),
+            current_time,
+        );
+    }
+
+    fn sync_stake(&self, stake: &mut Stake<Self::Api>) {
+        if !self.gap_mode_enabled().get() || stake.deferred_amount == 0 {
+            return;
+        }
+
+        if stake.distribution_id < self.distribution_id().get() {
+            let graduating_distribution_id = stake.distribution_id;
+            let acc_at_boundary = self.acc_reward_per_share_at(graduating_distribution_id).get();
+            stake.reward_debt += &stake.deferred_amount * &acc_at_boundary;
+            stake.amount += &stake.deferred_amount;
+            stake.deferred_amount = BigUint::zero();
+
+            let remaining_refs = self.distribution_ref_count(graduating_distribution_id).get();
+            if remaining_refs <= 1 {
+                self.distribution_ref_count(graduating_distribution_id).clear();
+                self.acc_reward_per_share_at(graduating_distribution_id).clear();
+            } else {
+                self.distribution_ref_count(graduating_distribution_id)
+                    .set(remaining_refs - 1);
+            }
+        }
     }
 
     fn send_rewards(&self, to: &ManagedAddress, amount: &BigUint) {
         if amount > &0 {
-            self.send().direct_egld(to, &amount);
+            self.total_rewards_paid().update(|val| *val += amount);
+            require!(
+                self.total_rewards_paid().get() <= self.total_rewards_funded().get(),
+                "Reward reserve exceeded"
+            );
+            self.send()
+                .direct_egld_or_single_esdt(to, &self.reward_token_id().get(), 0, amount);
         }
     }
 
     #[endpoint]
     fn claim_rewards(&self) {
+        require!(!self.paused().get(), "Contract is paused");
+
         let caller = self.blockchain().get_caller();
         self.update_pool();
 
         let mut stake = self.stakes(&caller).get();
+        self.sync_stake(&mut stake);
         require!(stake.amount > 0, "No staked amount");
 
-        let pending_reward = &stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt;
+        let pending_reward =
+            (&stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt) / ACC_PRECISION;
         require!(pending_reward > 0, "No rewards to claim");
 
         stake.reward_debt = &stake.amount * &self.acc_reward_per_share().get();
         self.stakes(&caller).set(&stake);
 
         self.send_rewards(&caller, &pending_reward);
+        self.claim_event(&caller, &pending_reward);
     }
 
     #[endpoint]
-    fn unstake(&self) {
+    fn unstake(&self, amount: BigUint) {
+        require!(!self.paused().get(), "Contract is paused");
+
         let caller = self.blockchain().get_caller();
         self.update_pool();
-    
+
         let mut stake = self.stakes(&caller).get();
+        self.sync_stake(&mut stake);
         require!(stake.amount > 0, "No staked amount");
-    
-        let pending_reward = &stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt;
-        
+        require!(amount > 0, "Must unstake more than 0");
+        require!(amount <= stake.amount, "Unstake amount exceeds staked amount");
+
+        let pending_reward =
+            (&stake.amount * &self.acc_reward_per_share().get() - &stake.reward_debt) / ACC_PRECISION;
+
         if pending_reward > 0 {
             self.send_rewards(&caller, &pending_reward);
         }
-    
-        let unstake_amount = stake.amount.clone();
-    
-        stake.amount = BigUint::zero();
-        stake.reward_debt = BigUint::zero();
+
+        stake.amount -= &amount;
+        stake.reward_debt = &stake.amount * &self.acc_reward_per_share().get();
         self.stakes(&caller).set(&stake);
-    
-        self.total_staked().update(|val| *val -= &unstake_amount);
-    
-        self.send().direct_egld(&caller, &unstake_amount);
+
+        self.total_staked().update(|val| *val -= &amount);
+
+        let unlock_time = self.blockchain().get_block_timestamp() + self.unbond_period_seconds().get();
+        let mut requests = self.unstake_requests(&caller).get();
+        requests.push(UnstakeRequest { amount: amount.clone(), unlock_time });
+        self.unstake_requests(&caller).set(&requests);
+
+        self.unstake_event(&caller, &amount);
     }
 
     #[endpoint]
-    #[payable("EGLD")]
+    fn withdraw(&self) {
+        let caller = self.blockchain().get_caller();
+        let current_time = self.blockchain().get_block_timestamp();
+
+        let requests = self.unstake_requests(&caller).get();
+        require!(!requests.is_empty(), "No pending unstake requests");
+
+        let mut releasable = BigUint::zero();
+        let mut remaining = ManagedVec::new();
+        for request in requests.iter() {
+            if request.unlock_time <= current_time {
+                releasable += &request.amount;
+            } else {
+                remaining.push(request.clone());
+            }
+        }
+
+        require!(releasable > 0, "No matured unstake requests yet");
+
+        self.unstake_requests(&caller).set(&remaining);
+        self.send()
+            .direct_egld_or_single_esdt(&caller, &self.staking_token_id().get(), 0, &releasable);
+    }
+
+    #[endpoint]
+    #[payable("*")]
     fn fundMe(&self) {
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(
+            payment.token_identifier == self.reward_token_id().get(),
+            "Invalid reward token"
+        );
+        let payment_amount = payment.amount;
+        self.reward_reserve().update(|val| *val += &payment_amount);
+        self.total_rewards_funded().update(|val| *val += &payment_amount);
+    }
+
+    #[only_owner]
+    #[endpoint(addAdmin)]
+    fn add_admin(&self, admin: ManagedAddress) {
+        self.admins().insert(admin);
+    }
+
+    #[only_owner]
+    #[endpoint(removeAdmin)]
+    fn remove_admin(&self, admin: ManagedAddress) {
+        self.admins().remove(&admin);
+    }
+
+    #[endpoint]
+    fn pause(&self) {
+        self.require_caller_is_owner_or_admin();
+        self.paused().set(true);
+    }
+
+    #[endpoint]
+    fn unpause(&self) {
+        self.require_caller_is_owner_or_admin();
+        self.paused().set(false);
+    }
+
+    fn require_caller_is_owner_or_admin(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.blockchain().get_owner_address() || self.admins().contains(&caller),
+            "Only the owner or an admin may call this endpoint"
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(setRewardPerSecond)]
+    fn set_reward_per_second(&self, reward_per_second: u64) {
+        self.update_pool();
+        self.reward_per_second().set(reward_per_second);
     }
 
 }
\ No newline at end of file
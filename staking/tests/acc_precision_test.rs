@@ -0,0 +1,102 @@
+use multiversx_sc::types::{Address, EgldOrEsdtTokenIdentifier};
+use multiversx_sc_scenario::{
+    rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper},
+    DebugApi,
+};
+use staking::*;
+
+const WASM_PATH: &str = "output/staking.wasm";
+const UNBOND_PERIOD_SECONDS: u64 = 60;
+const LARGE_STAKE: u64 = 1_000_000_000u64; // 1e9, dwarfs a reward_per_second of 1
+const SMALL_REWARD_PER_SECOND: u64 = 1u64;
+
+type StakingContractObj = staking::ContractObj<DebugApi>;
+
+struct StakingSetup<ObjBuilder>
+where
+    ObjBuilder: 'static + Copy + Fn() -> StakingContractObj,
+{
+    b_wrapper: BlockchainStateWrapper,
+    owner_address: Address,
+    contract_wrapper: ContractObjWrapper<StakingContractObj, ObjBuilder>,
+}
+
+fn setup(builder: impl 'static + Copy + Fn() -> StakingContractObj) -> StakingSetup<impl 'static + Copy + Fn() -> StakingContractObj> {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_wrapper = BlockchainStateWrapper::new();
+    let owner_address = b_wrapper.create_user_account(&rust_biguint!(1_000_000_000));
+    let contract_wrapper = b_wrapper.create_sc_account(&rust_zero, Some(&owner_address), builder, WASM_PATH);
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_zero, |sc| {
+            sc.init(
+                UNBOND_PERIOD_SECONDS,
+                false,
+                EgldOrEsdtTokenIdentifier::egld(),
+                EgldOrEsdtTokenIdentifier::egld(),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_biguint!(1_000_000_000), |sc| {
+            sc.fundMe();
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_zero, |sc| {
+            sc.set_reward_per_second(SMALL_REWARD_PER_SECOND);
+        })
+        .assert_ok();
+
+    StakingSetup {
+        b_wrapper,
+        owner_address,
+        contract_wrapper,
+    }
+}
+
+// A small reward_per_second against a large total_staked used to floor to 0 under
+// plain integer division (reward / total_staked). ACC_PRECISION must keep that
+// accrual non-zero, and reward_debt must stay in the same scaled units so
+// claim_rewards actually pays out the accrued amount.
+#[test]
+fn test_small_reward_against_large_stake_does_not_floor_to_zero() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(LARGE_STAKE));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(LARGE_STAKE), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(1);
+    setup
+        .b_wrapper
+        .execute_tx(&setup.owner_address, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.update_pool();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            assert!(sc.acc_reward_per_share().get() > 0u64);
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_rewards();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .check_egld_balance(&user, &rust_biguint!(1u64));
+}
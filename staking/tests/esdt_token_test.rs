@@ -0,0 +1,151 @@
+use multiversx_sc::types::{Address, EgldOrEsdtTokenIdentifier};
+use multiversx_sc_scenario::{
+    managed_biguint, rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper},
+    DebugApi,
+};
+use staking::*;
+
+const WASM_PATH: &str = "output/staking.wasm";
+const UNBOND_PERIOD_SECONDS: u64 = 60;
+const STAKING_TOKEN_ID: &[u8] = b"STAKE-abcdef";
+const REWARD_TOKEN_ID: &[u8] = b"REWARD-abcdef";
+const WRONG_TOKEN_ID: &[u8] = b"WRONG-abcdef";
+
+type StakingContractObj = staking::ContractObj<DebugApi>;
+
+struct StakingSetup<ObjBuilder>
+where
+    ObjBuilder: 'static + Copy + Fn() -> StakingContractObj,
+{
+    b_wrapper: BlockchainStateWrapper,
+    owner_address: Address,
+    contract_wrapper: ContractObjWrapper<StakingContractObj, ObjBuilder>,
+}
+
+fn setup(builder: impl 'static + Copy + Fn() -> StakingContractObj) -> StakingSetup<impl 'static + Copy + Fn() -> StakingContractObj> {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_wrapper = BlockchainStateWrapper::new();
+    let owner_address = b_wrapper.create_user_account(&rust_zero);
+    let contract_wrapper = b_wrapper.create_sc_account(&rust_zero, Some(&owner_address), builder, WASM_PATH);
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_zero, |sc| {
+            sc.init(
+                UNBOND_PERIOD_SECONDS,
+                false,
+                EgldOrEsdtTokenIdentifier::esdt(STAKING_TOKEN_ID),
+                EgldOrEsdtTokenIdentifier::esdt(REWARD_TOKEN_ID),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper.set_esdt_balance(&owner_address, REWARD_TOKEN_ID, &rust_biguint!(1_000_000));
+
+    b_wrapper
+        .execute_esdt_transfer(&owner_address, &contract_wrapper, REWARD_TOKEN_ID, 0, &rust_biguint!(1_000_000), |sc| {
+            sc.fundMe();
+        })
+        .assert_ok();
+
+    StakingSetup {
+        b_wrapper,
+        owner_address,
+        contract_wrapper,
+    }
+}
+
+// Paying `stake` with a token other than the configured staking token must be
+// rejected outright.
+#[test]
+fn test_stake_rejects_mismatched_token() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(0));
+    setup.b_wrapper.set_esdt_balance(&user, WRONG_TOKEN_ID, &rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_esdt_transfer(&user, &setup.contract_wrapper, WRONG_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+            sc.stake();
+        })
+        .assert_user_error("Invalid staking token");
+}
+
+// Paying `fundMe` with a token other than the configured reward token must be
+// rejected outright.
+#[test]
+fn test_fund_me_rejects_mismatched_token() {
+    let mut setup = setup(staking::contract_obj);
+    setup
+        .b_wrapper
+        .set_esdt_balance(&setup.owner_address, WRONG_TOKEN_ID, &rust_biguint!(1_000));
+
+    let owner_address = setup.owner_address.clone();
+    setup
+        .b_wrapper
+        .execute_esdt_transfer(&owner_address, &setup.contract_wrapper, WRONG_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+            sc.fundMe();
+        })
+        .assert_user_error("Invalid reward token");
+}
+
+// When the staking token and reward token differ, stake()/withdraw() must move
+// the staking token while claim_rewards()/send_rewards() must pay out the
+// separate reward token — neither path should route through the other's
+// identifier.
+#[test]
+fn test_distinct_staking_and_reward_tokens_route_correctly() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(0));
+    setup.b_wrapper.set_esdt_balance(&user, STAKING_TOKEN_ID, &rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_esdt_transfer(&user, &setup.contract_wrapper, STAKING_TOKEN_ID, 0, &rust_biguint!(1_000), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(1);
+    setup
+        .b_wrapper
+        .execute_tx(&setup.owner_address, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.update_pool();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_rewards();
+        })
+        .assert_ok();
+
+    // Reward paid out in the reward token, principal untouched.
+    setup
+        .b_wrapper
+        .check_esdt_balance(&user, REWARD_TOKEN_ID, &rust_biguint!(300));
+    setup
+        .b_wrapper
+        .check_esdt_balance(&user, STAKING_TOKEN_ID, &rust_biguint!(0));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(1_000));
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(1 + UNBOND_PERIOD_SECONDS);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.withdraw();
+        })
+        .assert_ok();
+
+    // Unbonded principal paid out in the staking token, not the reward token.
+    setup
+        .b_wrapper
+        .check_esdt_balance(&user, STAKING_TOKEN_ID, &rust_biguint!(1_000));
+}
@@ -0,0 +1,102 @@
+use multiversx_sc::types::{Address, EgldOrEsdtTokenIdentifier};
+use multiversx_sc_scenario::{
+    rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper},
+    DebugApi,
+};
+use staking::*;
+
+const WASM_PATH: &str = "output/staking.wasm";
+const UNBOND_PERIOD_SECONDS: u64 = 60;
+const SMALL_RESERVE: u64 = 100u64; // DEFAULT_REWARD_PER_SECOND (300) * 1s already exceeds this
+
+type StakingContractObj = staking::ContractObj<DebugApi>;
+
+struct StakingSetup<ObjBuilder>
+where
+    ObjBuilder: 'static + Copy + Fn() -> StakingContractObj,
+{
+    b_wrapper: BlockchainStateWrapper,
+    owner_address: Address,
+    contract_wrapper: ContractObjWrapper<StakingContractObj, ObjBuilder>,
+}
+
+fn setup(builder: impl 'static + Copy + Fn() -> StakingContractObj) -> StakingSetup<impl 'static + Copy + Fn() -> StakingContractObj> {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_wrapper = BlockchainStateWrapper::new();
+    let owner_address = b_wrapper.create_user_account(&rust_biguint!(SMALL_RESERVE));
+    let contract_wrapper = b_wrapper.create_sc_account(&rust_zero, Some(&owner_address), builder, WASM_PATH);
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_zero, |sc| {
+            sc.init(
+                UNBOND_PERIOD_SECONDS,
+                false,
+                EgldOrEsdtTokenIdentifier::egld(),
+                EgldOrEsdtTokenIdentifier::egld(),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_biguint!(SMALL_RESERVE), |sc| {
+            sc.fundMe();
+        })
+        .assert_ok();
+
+    StakingSetup {
+        b_wrapper,
+        owner_address,
+        contract_wrapper,
+    }
+}
+
+// When elapsed_time * reward_per_second outgrows the funded reserve, update_pool
+// must clamp accrual to what's actually left instead of minting reward out of
+// thin air, and the reserve must never go negative.
+#[test]
+fn test_update_pool_caps_accrual_to_reward_reserve() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(1_000), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    // 1 second at the default 300/s reward rate would uncap to 300, well above
+    // the 100 funded above.
+    setup.b_wrapper.set_block_timestamp(1);
+    setup
+        .b_wrapper
+        .execute_tx(&setup.owner_address, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.update_pool();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            assert_eq!(sc.reward_reserve().get(), 0u64);
+        })
+        .assert_ok();
+
+    // A further tick must not drive the reserve negative: it simply accrues
+    // nothing more until `fundMe` tops it back up.
+    setup.b_wrapper.set_block_timestamp(2);
+    setup
+        .b_wrapper
+        .execute_tx(&setup.owner_address, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.update_pool();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            assert_eq!(sc.reward_reserve().get(), 0u64);
+        })
+        .assert_ok();
+}
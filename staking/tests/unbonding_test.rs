@@ -0,0 +1,201 @@
+use multiversx_sc::types::{Address, EgldOrEsdtTokenIdentifier};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper},
+    DebugApi,
+};
+use staking::*;
+
+const WASM_PATH: &str = "output/staking.wasm";
+const UNBOND_PERIOD_SECONDS: u64 = 100;
+
+type StakingContractObj = staking::ContractObj<DebugApi>;
+
+struct StakingSetup<ObjBuilder>
+where
+    ObjBuilder: 'static + Copy + Fn() -> StakingContractObj,
+{
+    b_wrapper: BlockchainStateWrapper,
+    owner_address: Address,
+    contract_wrapper: ContractObjWrapper<StakingContractObj, ObjBuilder>,
+}
+
+fn setup(builder: impl 'static + Copy + Fn() -> StakingContractObj) -> StakingSetup<impl 'static + Copy + Fn() -> StakingContractObj> {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_wrapper = BlockchainStateWrapper::new();
+    let owner_address = b_wrapper.create_user_account(&rust_biguint!(1_000_000));
+    let contract_wrapper = b_wrapper.create_sc_account(&rust_zero, Some(&owner_address), builder, WASM_PATH);
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_zero, |sc| {
+            sc.init(
+                UNBOND_PERIOD_SECONDS,
+                false,
+                EgldOrEsdtTokenIdentifier::egld(),
+                EgldOrEsdtTokenIdentifier::egld(),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&owner_address, &contract_wrapper, &rust_biguint!(1_000_000), |sc| {
+            sc.fundMe();
+        })
+        .assert_ok();
+
+    StakingSetup {
+        b_wrapper,
+        owner_address,
+        contract_wrapper,
+    }
+}
+
+// Unstaking less than the full staked balance must leave the remainder staked
+// and recompute reward_debt against that smaller amount, not the original one.
+#[test]
+fn test_partial_unstake_recomputes_reward_debt() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(1_000), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(400));
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            let stake = sc.stakes(&managed_address!(&user)).get();
+            assert_eq!(stake.amount, managed_biguint!(600));
+            assert_eq!(
+                stake.reward_debt,
+                &managed_biguint!(600) * &sc.acc_reward_per_share().get()
+            );
+            assert_eq!(sc.total_staked().get(), managed_biguint!(600));
+        })
+        .assert_ok();
+}
+
+// Multiple unstake requests with different unlock times must all be tracked
+// independently and not clobber each other.
+#[test]
+fn test_multiple_concurrent_unstake_requests() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(1_000), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(10);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(100));
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(20);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(200));
+        })
+        .assert_ok();
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            let requests = sc.unstake_requests(&managed_address!(&user)).get();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests.get(0).amount, managed_biguint!(100));
+            assert_eq!(requests.get(0).unlock_time, 10 + UNBOND_PERIOD_SECONDS);
+            assert_eq!(requests.get(1).amount, managed_biguint!(200));
+            assert_eq!(requests.get(1).unlock_time, 20 + UNBOND_PERIOD_SECONDS);
+        })
+        .assert_ok();
+}
+
+// withdraw must release only the requests whose unlock_time has passed and
+// leave the still-unmatured ones in place for a later call.
+#[test]
+fn test_withdraw_releases_only_matured_requests() {
+    let mut setup = setup(staking::contract_obj);
+    let user = setup.b_wrapper.create_user_account(&rust_biguint!(1_000));
+
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(1_000), |sc| {
+            sc.stake();
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(10);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(100));
+        })
+        .assert_ok();
+
+    setup.b_wrapper.set_block_timestamp(20);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.unstake(managed_biguint!(200));
+        })
+        .assert_ok();
+
+    // Only the first request (unlocking at 10 + UNBOND_PERIOD_SECONDS) has matured.
+    setup.b_wrapper.set_block_timestamp(10 + UNBOND_PERIOD_SECONDS);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.withdraw();
+        })
+        .assert_ok();
+
+    // Each unstake also paid out pending EGLD rewards: 3000 at t=10 and 2999 at
+    // t=20, on top of the 100 principal released here.
+    setup.b_wrapper.check_egld_balance(&user, &rust_biguint!(6_099));
+
+    setup
+        .b_wrapper
+        .execute_query(&setup.contract_wrapper, |sc| {
+            let requests = sc.unstake_requests(&managed_address!(&user)).get();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests.get(0).amount, managed_biguint!(200));
+        })
+        .assert_ok();
+
+    // Calling withdraw again before the second request matures must reject.
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.withdraw();
+        })
+        .assert_user_error("No matured unstake requests yet");
+
+    // Once it matures too, the remaining amount is released.
+    setup.b_wrapper.set_block_timestamp(20 + UNBOND_PERIOD_SECONDS);
+    setup
+        .b_wrapper
+        .execute_tx(&user, &setup.contract_wrapper, &rust_biguint!(0), |sc| {
+            sc.withdraw();
+        })
+        .assert_ok();
+
+    setup.b_wrapper.check_egld_balance(&user, &rust_biguint!(6_299));
+}